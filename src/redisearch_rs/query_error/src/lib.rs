@@ -71,6 +71,7 @@ pub enum QueryErrorCode {
     WeightNotAllowed,
     VectorNotAllowed,
     OutOfMemory,
+    IncompatibleFederatedSources,
 }
 
 impl Debug for QueryErrorCode {
@@ -85,11 +86,175 @@ impl Display for QueryErrorCode {
     }
 }
 
+/// Coarse classification of a [`QueryErrorCode`], letting clients branch on
+/// error class programmatically instead of substring-matching the message
+/// text returned by [`QueryErrorCode::to_c_str`].
+///
+/// cbindgen:prefix-with-name
+/// cbindgen:rename-all=ScreamingSnakeCase
+#[derive(Clone, Copy, Debug, FromRepr, PartialEq, Eq)]
+#[repr(u8)]
+pub enum QueryErrorCategory {
+    /// The query could not be parsed.
+    Syntax,
+    /// The query parsed, but is semantically invalid (bad option, bad value,
+    /// conflicting/duplicate field or parameter, unsupported combination).
+    Validation,
+    /// The query could not be served because a referenced resource (index,
+    /// reducer, document) does not exist.
+    NotFound,
+    /// The query could not be served due to resource exhaustion (out of
+    /// memory, a limit was exceeded).
+    Resource,
+    /// The query failed while executing, for reasons unrelated to the query
+    /// itself (plan construction, distribution, timeout, index mismatch).
+    Runtime,
+    /// The query used a feature or attribute that is not allowed in this
+    /// context.
+    Unsupported,
+}
+
 impl QueryErrorCode {
     pub const fn is_ok(self) -> bool {
         matches!(self, Self::Ok)
     }
 
+    /// A stable, machine-readable identifier for this error code, e.g.
+    /// `"search_syntax"` or `"search_index_not_found"`. Unlike
+    /// [`QueryErrorCode::to_c_str`], this never changes across releases and
+    /// carries no human-readable message, so it's safe for clients to branch
+    /// on.
+    ///
+    /// This crate's snapshot has no `extern "C"`/cbindgen bridge for any of
+    /// `QueryErrorCode`, this function included; exposing it to C callers
+    /// (alongside `to_c_str`) is wiring that lives outside this tree.
+    pub const fn machine_code(self) -> &'static CStr {
+        match self {
+            Self::Ok => c"ok",
+            Self::Generic => c"search_generic",
+            Self::Syntax => c"search_syntax",
+            Self::ParseArgs => c"search_parse_args",
+            Self::AddArgs => c"search_add_args",
+            Self::Expr => c"search_expr",
+            Self::Keyword => c"search_keyword",
+            Self::NoResults => c"search_no_results",
+            Self::BadAttr => c"search_bad_attr",
+            Self::Inval => c"search_inval",
+            Self::BuildPlan => c"search_build_plan",
+            Self::ConstructPipeline => c"search_construct_pipeline",
+            Self::NoReducer => c"search_no_reducer",
+            Self::ReducerGeneric => c"search_reducer_generic",
+            Self::AggPlan => c"search_agg_plan",
+            Self::CursorAlloc => c"search_cursor_alloc",
+            Self::ReducerInit => c"search_reducer_init",
+            Self::QString => c"search_qstring",
+            Self::NoPropKey => c"search_no_prop_key",
+            Self::NoPropVal => c"search_no_prop_val",
+            Self::NoDoc => c"search_no_doc",
+            Self::NoOption => c"search_no_option",
+            Self::RedisKeyType => c"search_redis_key_type",
+            Self::InvalPath => c"search_inval_path",
+            Self::IndexExists => c"search_index_exists",
+            Self::BadOption => c"search_bad_option",
+            Self::BadOrderOption => c"search_bad_order_option",
+            Self::Limit => c"search_limit",
+            Self::NoIndex => c"search_index_not_found",
+            Self::DocExists => c"search_doc_exists",
+            Self::DocNotAdded => c"search_doc_not_added",
+            Self::DupField => c"search_dup_field",
+            Self::GeoFormat => c"search_geo_format",
+            Self::NoDistribute => c"search_no_distribute",
+            Self::UnsuppType => c"search_unsupp_type",
+            Self::NotNumeric => c"search_not_numeric",
+            Self::TimedOut => c"search_timed_out",
+            Self::NoParam => c"search_param_not_found",
+            Self::DupParam => c"search_dup_param",
+            Self::BadVal => c"search_bad_val",
+            Self::NonHybrid => c"search_non_hybrid",
+            Self::HybridNonExist => c"search_hybrid_non_exist",
+            Self::AdhocWithBatchSize => c"search_adhoc_with_batch_size",
+            Self::AdhocWithEfRuntime => c"search_adhoc_with_ef_runtime",
+            Self::NonRange => c"search_non_range",
+            Self::Missing => c"search_missing",
+            Self::Mismatch => c"search_mismatch",
+            Self::UnknownIndex => c"search_index_not_found",
+            Self::DroppedBackground => c"search_dropped_background",
+            Self::AliasConflict => c"search_alias_conflict",
+            Self::IndexBgOOMFail => c"search_index_bg_oom_fail",
+            Self::WeightNotAllowed => c"search_weight_not_allowed",
+            Self::VectorNotAllowed => c"search_vector_not_allowed",
+            Self::OutOfMemory => c"search_out_of_memory",
+            Self::IncompatibleFederatedSources => c"search_incompatible_federated_sources",
+        }
+    }
+
+    /// The [`QueryErrorCategory`] this error code belongs to.
+    ///
+    /// As with [`QueryErrorCode::machine_code`], no C binding for this
+    /// exists in this tree; see that function's doc comment.
+    pub const fn category(self) -> QueryErrorCategory {
+        match self {
+            Self::Syntax
+            | Self::ParseArgs
+            | Self::AddArgs
+            | Self::Expr
+            | Self::Keyword
+            | Self::QString
+            | Self::GeoFormat
+            | Self::BadAttr => QueryErrorCategory::Syntax,
+
+            Self::Inval
+            | Self::NoPropKey
+            | Self::NoPropVal
+            | Self::NoOption
+            | Self::RedisKeyType
+            | Self::InvalPath
+            | Self::BadOption
+            | Self::BadOrderOption
+            | Self::DupField
+            | Self::NotNumeric
+            | Self::NoParam
+            | Self::DupParam
+            | Self::BadVal
+            | Self::NonHybrid
+            | Self::HybridNonExist
+            | Self::NonRange
+            | Self::Missing
+            | Self::DocNotAdded
+            | Self::IndexExists
+            | Self::DocExists
+            | Self::AliasConflict => QueryErrorCategory::Validation,
+
+            Self::OutOfMemory | Self::IndexBgOOMFail | Self::Limit | Self::CursorAlloc => {
+                QueryErrorCategory::Resource
+            }
+
+            Self::NoIndex | Self::UnknownIndex | Self::NoReducer | Self::NoDoc => {
+                QueryErrorCategory::NotFound
+            }
+
+            Self::WeightNotAllowed
+            | Self::VectorNotAllowed
+            | Self::UnsuppType
+            | Self::AdhocWithBatchSize
+            | Self::AdhocWithEfRuntime
+            | Self::IncompatibleFederatedSources => QueryErrorCategory::Unsupported,
+
+            Self::Ok
+            | Self::Generic
+            | Self::BuildPlan
+            | Self::ConstructPipeline
+            | Self::ReducerGeneric
+            | Self::AggPlan
+            | Self::ReducerInit
+            | Self::NoDistribute
+            | Self::TimedOut
+            | Self::Mismatch
+            | Self::DroppedBackground
+            | Self::NoResults => QueryErrorCategory::Runtime,
+        }
+    }
+
     // TODO(enricozb): this should be moved to either a thiserror or strum macro.
     // This is done as &'static CStr because we need to provide *const c_char
     // representations of the error codes for FFI into C code.
@@ -149,6 +314,9 @@ impl QueryErrorCode {
             Self::WeightNotAllowed => c"SEARCH_WEIGHT_NOT_ALLOWED: Weight attributes are not allowed",
             Self::VectorNotAllowed => c"SEARCH_VECTOR_NOT_ALLOWED: Vector queries are not allowed",
             Self::OutOfMemory => c"SEARCH_OUT_OF_MEMORY: Not enough memory available to execute the query",
+            Self::IncompatibleFederatedSources => {
+                c"SEARCH_INCOMPATIBLE_FEDERATED_SOURCES: Cannot merge results from these source kinds in a single federated query"
+            }
         }
     }
 }
@@ -175,6 +343,12 @@ impl QueryError {
         self.code
     }
 
+    /// The [`QueryErrorCategory`] of [`QueryError::code`], attached
+    /// automatically so reply serialization can emit `{code, category, message}`.
+    pub const fn category(&self) -> QueryErrorCategory {
+        self.code.category()
+    }
+
     pub const fn set_code(&mut self, code: QueryErrorCode) {
         if !self.is_ok() {
             return;
@@ -218,26 +392,177 @@ impl QueryError {
     }
 }
 
+/// A soft condition worth surfacing to the client without failing the query,
+/// e.g. partial results from a timed-out shard, a truncated fuzzy expansion,
+/// a dropped sort key, or degraded vector recall.
+///
+/// cbindgen:prefix-with-name
+/// cbindgen:rename-all=ScreamingSnakeCase
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum WarningCode {
+    ReachedMaxPrefixExpansions,
+    OutOfMemory,
+    PartialResults,
+    TruncatedFuzzyExpansion,
+    DroppedSortKey,
+    DegradedVectorRecall,
+}
+
+impl WarningCode {
+    pub const fn to_c_str(self) -> &'static CStr {
+        match self {
+            Self::ReachedMaxPrefixExpansions => c"Reached the maximum number of prefix expansions",
+            Self::OutOfMemory => c"Out of memory",
+            Self::PartialResults => c"Partial results: a shard timed out",
+            Self::TruncatedFuzzyExpansion => c"Fuzzy expansion was truncated",
+            Self::DroppedSortKey => c"A sort key was dropped from the results",
+            Self::DegradedVectorRecall => c"Vector search recall was degraded",
+        }
+    }
+}
+
+/// An ordered, de-duplicated collection of [`WarningCode`]s (each with an
+/// optional detail message) collected while planning or executing a query.
 #[derive(Clone, Debug, Default)]
 pub struct Warnings {
-    reached_max_prefix_expansions: bool,
-    out_of_memory: bool,
+    entries: Vec<(WarningCode, Option<CString>)>,
 }
 
 impl Warnings {
-    pub const fn reached_max_prefix_expansions(&self) -> bool {
-        self.reached_max_prefix_expansions
+    /// Records `code`, along with an optional `detail` message. A no-op if
+    /// `code` was already recorded, so repeated soft failures of the same
+    /// kind don't spam the client.
+    pub fn push(&mut self, code: WarningCode, detail: Option<CString>) {
+        if self.entries.iter().any(|(existing, _)| *existing == code) {
+            return;
+        }
+
+        self.entries.push((code, detail));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (WarningCode, Option<&CStr>)> {
+        self.entries
+            .iter()
+            .map(|(code, detail)| (*code, detail.as_deref()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Compatibility shims over `push`/`iter` for the two warnings that used
+    // to be hardcoded booleans; callers should migrate to `push`/`iter`.
+
+    pub fn reached_max_prefix_expansions(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|(code, _)| *code == WarningCode::ReachedMaxPrefixExpansions)
+    }
+
+    pub fn set_reached_max_prefix_expansions(&mut self) {
+        self.push(WarningCode::ReachedMaxPrefixExpansions, None);
+    }
+
+    pub fn out_of_memory(&self) -> bool {
+        self.entries
+            .iter()
+            .any(|(code, _)| *code == WarningCode::OutOfMemory)
     }
 
-    pub const fn set_reached_max_prefix_expansions(&mut self) {
-        self.reached_max_prefix_expansions = true;
+    pub fn set_out_of_memory(&mut self) {
+        self.push(WarningCode::OutOfMemory, None);
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_is_noop_for_repeated_code() {
+        let mut warnings = Warnings::default();
 
-    pub const fn out_of_memory(&self) -> bool {
-        self.out_of_memory
+        warnings.push(WarningCode::OutOfMemory, Some(c"first".to_owned()));
+        warnings.push(WarningCode::OutOfMemory, Some(c"second".to_owned()));
+
+        let entries: Vec<_> = warnings.iter().collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0], (WarningCode::OutOfMemory, Some(c"first")));
     }
 
-    pub const fn set_out_of_memory(&mut self) {
-        self.out_of_memory = true;
+    #[test]
+    fn test_iter_preserves_insertion_order() {
+        let mut warnings = Warnings::default();
+
+        warnings.push(WarningCode::DroppedSortKey, None);
+        warnings.push(WarningCode::OutOfMemory, None);
+        warnings.push(WarningCode::PartialResults, None);
+
+        let codes: Vec<_> = warnings.iter().map(|(code, _)| code).collect();
+        assert_eq!(
+            codes,
+            [
+                WarningCode::DroppedSortKey,
+                WarningCode::OutOfMemory,
+                WarningCode::PartialResults,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_category_spot_checks() {
+        assert_eq!(
+            QueryErrorCode::Syntax.category(),
+            QueryErrorCategory::Syntax
+        );
+        assert_eq!(
+            QueryErrorCode::BadOption.category(),
+            QueryErrorCategory::Validation
+        );
+        assert_eq!(
+            QueryErrorCode::OutOfMemory.category(),
+            QueryErrorCategory::Resource
+        );
+        assert_eq!(
+            QueryErrorCode::NoReducer.category(),
+            QueryErrorCategory::NotFound
+        );
+        assert_eq!(
+            QueryErrorCode::WeightNotAllowed.category(),
+            QueryErrorCategory::Unsupported
+        );
+        assert_eq!(
+            QueryErrorCode::Mismatch.category(),
+            QueryErrorCategory::Runtime
+        );
+    }
+
+    #[test]
+    fn test_every_category_has_at_least_one_code() {
+        let mut seen = Vec::new();
+
+        for repr in 0..=u8::MAX {
+            if let Some(code) = QueryErrorCode::from_repr(repr) {
+                let category = code.category();
+                if !seen.contains(&category) {
+                    seen.push(category);
+                }
+            }
+        }
+
+        for category in [
+            QueryErrorCategory::Syntax,
+            QueryErrorCategory::Validation,
+            QueryErrorCategory::NotFound,
+            QueryErrorCategory::Resource,
+            QueryErrorCategory::Runtime,
+            QueryErrorCategory::Unsupported,
+        ] {
+            assert!(
+                seen.contains(&category),
+                "no QueryErrorCode maps to {category:?}",
+            );
+        }
     }
 }
@@ -0,0 +1,238 @@
+/*
+ * Copyright (c) 2006-Present, Redis Ltd.
+ * All rights reserved.
+ *
+ * Licensed under your choice of the Redis Source Available License 2.0
+ * (RSALv2); or (b) the Server Side Public License v1 (SSPLv1); or (c) the
+ * GNU Affero General Public License v3 (AGPLv3).
+*/
+
+//! Weighted multi-source union iterator for federated queries across indexes.
+
+use ffi::t_docId;
+use inverted_index::RSIndexResult;
+use query_error::QueryErrorCode;
+
+use crate::{RQEIterator, RQEIteratorError, RQEValidateStatus, SkipToOutcome};
+
+/// The kind of index/shard a [`FederatedSource`] draws results from. Some
+/// kinds don't support being boosted/attenuated by a [`FederatedSource::weight`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SourceKind {
+    /// A shard of the local index.
+    LocalShard,
+    /// A remote index reached over the network.
+    RemoteIndex,
+    /// Results computed on the fly (e.g. a synthetic or streaming result
+    /// set) rather than read from an index. It has no relevance score of
+    /// its own to scale, and the remote side of a [`Self::RemoteIndex`]
+    /// source has no way to account for a local-only contributor that isn't
+    /// backed by an index, so the two can't be merged.
+    Ephemeral,
+}
+
+impl SourceKind {
+    /// Whether this source kind allows a non-default [`FederatedSource::weight`].
+    fn allows_weight(self) -> bool {
+        !matches!(self, Self::Ephemeral)
+    }
+
+    /// Whether `self` can be merged with `other` in the same [`Federated`] iterator.
+    fn compatible_with(self, other: Self) -> bool {
+        !matches!(
+            (self, other),
+            (Self::Ephemeral, Self::RemoteIndex) | (Self::RemoteIndex, Self::Ephemeral)
+        )
+    }
+}
+
+/// One child of a [`Federated`] iterator: its source kind, the weight
+/// multiplier applied to its results, and the iterator itself.
+pub struct FederatedSource<'index> {
+    pub kind: SourceKind,
+    pub weight: f64,
+    pub iter: Box<dyn RQEIterator<'index> + 'index>,
+}
+
+/// Merges results from several child iterators drawn from different
+/// indexes/shards, applying a distinct [`FederatedSource::weight`] multiplier
+/// per source before merging on `doc_id` order. Lets a federated search boost
+/// or attenuate specific sources.
+pub struct Federated<'index> {
+    result: RSIndexResult<'index>,
+    sources: Vec<FederatedSource<'index>>,
+}
+
+impl<'index> Federated<'index> {
+    /// Creates a new [`Federated`] iterator merging `sources`.
+    ///
+    /// Returns [`QueryErrorCode::WeightNotAllowed`] if a source carries a
+    /// non-default weight while its [`SourceKind`] forbids it, or
+    /// [`QueryErrorCode::IncompatibleFederatedSources`] if the given sources'
+    /// kinds cannot be merged together.
+    pub fn new(sources: Vec<FederatedSource<'index>>) -> Result<Self, QueryErrorCode> {
+        for source in &sources {
+            if source.weight != 1.0 && !source.kind.allows_weight() {
+                return Err(QueryErrorCode::WeightNotAllowed);
+            }
+        }
+
+        for (i, a) in sources.iter().enumerate() {
+            for b in &sources[i + 1..] {
+                if !a.kind.compatible_with(b.kind) {
+                    return Err(QueryErrorCode::IncompatibleFederatedSources);
+                }
+            }
+        }
+
+        Ok(Self {
+            result: RSIndexResult::virt(),
+            sources,
+        })
+    }
+
+    fn all_sources_exhausted(&self) -> bool {
+        self.sources.iter().all(|source| source.iter.at_eof())
+    }
+
+    /// Among sources not yet exhausted, the smallest `last_doc_id`, i.e. the
+    /// next docId this iterator would yield.
+    fn next_doc_id(&self) -> Option<t_docId> {
+        self.sources
+            .iter()
+            .filter(|source| !source.iter.at_eof())
+            .map(|source| source.iter.last_doc_id())
+            .min()
+    }
+
+    /// Sums the weighted contribution of every source currently sitting on
+    /// `doc_id` into `self.result`.
+    fn merge_at(&mut self, doc_id: t_docId) {
+        self.result.doc_id = doc_id;
+        self.result.weight = self
+            .sources
+            .iter()
+            .filter(|source| source.iter.last_doc_id() == doc_id)
+            .map(|source| source.weight)
+            .sum();
+    }
+}
+
+impl<'index> RQEIterator<'index> for Federated<'index> {
+    fn current(&mut self) -> Option<&mut RSIndexResult<'index>> {
+        Some(&mut self.result)
+    }
+
+    fn read(&mut self) -> Result<Option<&mut RSIndexResult<'index>>, RQEIteratorError> {
+        if self.at_eof() {
+            return Ok(None);
+        }
+
+        // Advance every source still sitting on the current result's docId so
+        // it isn't re-merged into the next result.
+        for source in &mut self.sources {
+            if !source.iter.at_eof() && source.iter.last_doc_id() == self.result.doc_id {
+                source.iter.read()?;
+            }
+        }
+
+        let Some(doc_id) = self.next_doc_id() else {
+            return Ok(None);
+        };
+
+        self.merge_at(doc_id);
+        Ok(Some(&mut self.result))
+    }
+
+    fn skip_to(
+        &mut self,
+        doc_id: t_docId,
+    ) -> Result<Option<SkipToOutcome<'_, 'index>>, RQEIteratorError> {
+        debug_assert!(doc_id > self.result.doc_id);
+
+        if self.all_sources_exhausted() {
+            return Ok(None);
+        }
+
+        for source in &mut self.sources {
+            if !source.iter.at_eof() && source.iter.last_doc_id() < doc_id {
+                source.iter.skip_to(doc_id)?;
+            }
+        }
+
+        let Some(next_doc_id) = self.next_doc_id() else {
+            return Ok(None);
+        };
+
+        self.merge_at(next_doc_id);
+
+        if next_doc_id == doc_id {
+            Ok(Some(SkipToOutcome::Found(&mut self.result)))
+        } else {
+            Ok(Some(SkipToOutcome::NotFound(&mut self.result)))
+        }
+    }
+
+    fn revalidate(&mut self) -> Result<RQEValidateStatus<'_, 'index>, RQEIteratorError> {
+        let mut current_result_affected = false;
+
+        let mut i = 0;
+        while i < self.sources.len() {
+            let last_doc_id = self.sources[i].iter.last_doc_id();
+
+            match self.sources[i].iter.revalidate()? {
+                RQEValidateStatus::Aborted => {
+                    // Drop only the aborted source; the federation continues
+                    // merging the remaining ones.
+                    if last_doc_id == self.result.doc_id {
+                        current_result_affected = true;
+                    }
+                    self.sources.remove(i);
+                    continue;
+                }
+                RQEValidateStatus::Moved { .. } => {
+                    if last_doc_id == self.result.doc_id {
+                        current_result_affected = true;
+                    }
+                }
+                RQEValidateStatus::Ok => {}
+            }
+
+            i += 1;
+        }
+
+        if current_result_affected {
+            Ok(RQEValidateStatus::Moved {
+                current: self.read()?,
+            })
+        } else {
+            Ok(RQEValidateStatus::Ok)
+        }
+    }
+
+    #[inline(always)]
+    fn rewind(&mut self) {
+        self.result.doc_id = 0;
+        for source in &mut self.sources {
+            source.iter.rewind();
+        }
+    }
+
+    #[inline(always)]
+    fn num_estimated(&self) -> usize {
+        self.sources
+            .iter()
+            .map(|source| source.iter.num_estimated())
+            .sum()
+    }
+
+    #[inline(always)]
+    fn last_doc_id(&self) -> t_docId {
+        self.result.doc_id
+    }
+
+    #[inline(always)]
+    fn at_eof(&self) -> bool {
+        self.all_sources_exhausted()
+    }
+}
@@ -0,0 +1,142 @@
+/*
+ * Copyright (c) 2006-Present, Redis Ltd.
+ * All rights reserved.
+ *
+ * Licensed under your choice of the Redis Source Available License 2.0
+ * (RSALv2); or (b) the Server Side Public License v1 (SSPLv1); or (c) the
+ * GNU Affero General Public License v3 (AGPLv3).
+*/
+
+//! Generation tokens for detecting that an index was structurally mutated
+//! out from under a long-running iteration, e.g. by background block GC, a
+//! field reindex, or an alias retarget.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use ffi::t_docId;
+use inverted_index::RSIndexResult;
+
+use crate::{RQEIterator, RQEIteratorError, RQEValidateStatus, SkipToOutcome};
+
+/// Owned by an index; bumped on every structural mutation. Leaf
+/// [`crate::RQEIterator`]s capture the [`Generation`] current at construction
+/// time and compare it against the live value during
+/// [`crate::RQEIterator::revalidate`]. A mismatch means the index was
+/// swapped, recycled, or had its schema changed mid-walk, and the leaf must
+/// report [`crate::RQEValidateStatus::Aborted`] rather than risk returning
+/// results derived from a different generation of the index.
+#[derive(Debug, Default)]
+pub struct GenerationCounter(AtomicU64);
+
+impl GenerationCounter {
+    pub const fn new() -> Self {
+        Self(AtomicU64::new(0))
+    }
+
+    /// The current generation, to be captured by a leaf iterator at
+    /// construction time.
+    pub fn current(&self) -> Generation {
+        Generation(self.0.load(Ordering::Acquire))
+    }
+
+    /// Bumps the generation. Called by the index whenever it is structurally
+    /// mutated (block GC, field reindex, alias retarget).
+    pub fn bump(&self) {
+        self.0.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// A snapshot of a [`GenerationCounter`], captured by a leaf iterator at
+/// construction and compared against the live counter in `revalidate`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Generation(u64);
+
+impl Generation {
+    /// Whether this (captured-at-construction) generation still matches
+    /// `live`, read from the index's current [`GenerationCounter`]. `false`
+    /// means the index was mutated since construction and the leaf holding
+    /// this generation must abort.
+    pub fn matches(self, live: Generation) -> bool {
+        self == live
+    }
+}
+
+/// Wraps a leaf [`RQEIterator`] so it captures its owning index's
+/// [`Generation`] at construction and aborts from [`RQEIterator::revalidate`]
+/// if the index was structurally mutated since, rather than delegating
+/// straight to the wrapped leaf's own revalidation, which has no way to
+/// learn about such mutations on its own.
+pub struct GenerationGuarded<'index, Leaf> {
+    counter: &'index GenerationCounter,
+    captured: Generation,
+    leaf: Leaf,
+}
+
+impl<'index, Leaf> GenerationGuarded<'index, Leaf>
+where
+    Leaf: RQEIterator<'index>,
+{
+    /// Creates a new [`GenerationGuarded`] wrapping `leaf`, capturing
+    /// `counter`'s current [`Generation`].
+    pub fn new(counter: &'index GenerationCounter, leaf: Leaf) -> Self {
+        Self {
+            counter,
+            captured: counter.current(),
+            leaf,
+        }
+    }
+}
+
+impl<'index, Leaf> RQEIterator<'index> for GenerationGuarded<'index, Leaf>
+where
+    Leaf: RQEIterator<'index>,
+{
+    #[inline(always)]
+    fn current(&mut self) -> Option<&mut RSIndexResult<'index>> {
+        self.leaf.current()
+    }
+
+    #[inline(always)]
+    fn read(&mut self) -> Result<Option<&mut RSIndexResult<'index>>, RQEIteratorError> {
+        self.leaf.read()
+    }
+
+    #[inline(always)]
+    fn skip_to(
+        &mut self,
+        doc_id: t_docId,
+    ) -> Result<Option<SkipToOutcome<'_, 'index>>, RQEIteratorError> {
+        self.leaf.skip_to(doc_id)
+    }
+
+    fn revalidate(&mut self) -> Result<RQEValidateStatus<'_, 'index>, RQEIteratorError> {
+        if !self.captured.matches(self.counter.current()) {
+            // The index was mutated since construction; the leaf may be
+            // holding onto data from a different generation, so abort
+            // without ever consulting it.
+            return Ok(RQEValidateStatus::Aborted);
+        }
+
+        self.leaf.revalidate()
+    }
+
+    #[inline(always)]
+    fn rewind(&mut self) {
+        self.leaf.rewind();
+    }
+
+    #[inline(always)]
+    fn num_estimated(&self) -> usize {
+        self.leaf.num_estimated()
+    }
+
+    #[inline(always)]
+    fn last_doc_id(&self) -> t_docId {
+        self.leaf.last_doc_id()
+    }
+
+    #[inline(always)]
+    fn at_eof(&self) -> bool {
+        self.leaf.at_eof()
+    }
+}
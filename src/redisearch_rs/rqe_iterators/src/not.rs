@@ -0,0 +1,202 @@
+/*
+ * Copyright (c) 2006-Present, Redis Ltd.
+ * All rights reserved.
+ *
+ * Licensed under your choice of the Redis Source Available License 2.0
+ * (RSALv2); or (b) the Server Side Public License v1 (SSPLv1); or (c) the
+ * GNU Affero General Public License v3 (AGPLv3).
+*/
+
+//! Not iterator implementation
+
+use ffi::{RS_FIELDMASK_ALL, t_docId};
+use inverted_index::RSIndexResult;
+
+use crate::{RQEIterator, RQEIteratorError, RQEValidateStatus, SkipToOutcome};
+
+/// Iterator that yields every docId in `[1, max_doc_id]` that is *absent*
+/// from the wrapped [`Not::child`] iterator, i.e. the complement of `child`
+/// over that range. This is the negation counterpart to [`crate::optional::Optional`],
+/// which fills gaps with virtual results instead of yielding only the gaps.
+pub struct Not<'index, Child> {
+    /// Inclusive upper bound on document identifiers to iterate over.
+    max_doc_id: t_docId,
+
+    /// Weight applied to every virtual result produced by this [`Not`] iterator.
+    weight: f64,
+
+    result: RSIndexResult<'index>,
+
+    /// The child [`RQEIterator`] provided at construction time, used while
+    /// it can still tell us which docIds it contains.
+    ///
+    /// In case the child aborts during [`RQEIterator::revalidate`], this is
+    /// turned into [`None`], changed from the [`Some`] state it starts at
+    /// when created via [`Not::new`]. From that point onward the child can
+    /// no longer be consulted, so every remaining docId up to `max_doc_id`
+    /// is treated as absent from it (a pure wildcard), mirroring how
+    /// [`crate::optional::Optional`] drops its child on abort.
+    child: Option<Child>,
+}
+
+impl<'index, Child> Not<'index, Child>
+where
+    Child: RQEIterator<'index>,
+{
+    #[inline(always)]
+    /// Creates a new [`Not`] iterator.
+    ///
+    /// * `max_id` is the upper bound of document identifiers visited by
+    ///   [`RQEIterator::read`] and [`RQEIterator::skip_to`].
+    /// * `weight` is applied to every [`RSIndexResult`] yielded by this
+    ///   iterator (all of which are virtual, since a "hit" here means the
+    ///   `child` does *not* have a result for that docId).
+    /// * `child` [`RQEIterator`] whose hits are excluded from this iterator.
+    pub const fn new(max_id: t_docId, weight: f64, child: Child) -> Self {
+        Self {
+            max_doc_id: max_id,
+            weight,
+            result: RSIndexResult::virt()
+                .frequency(1)
+                .field_mask(RS_FIELDMASK_ALL),
+            child: Some(child),
+        }
+    }
+}
+
+impl<'index, Child> RQEIterator<'index> for Not<'index, Child>
+where
+    Child: RQEIterator<'index>,
+{
+    fn current(&mut self) -> Option<&mut RSIndexResult<'index>> {
+        Some(&mut self.result)
+    }
+
+    fn read(&mut self) -> Result<Option<&mut RSIndexResult<'index>>, RQEIteratorError> {
+        if self.at_eof() {
+            return Ok(None);
+        }
+
+        loop {
+            self.result.doc_id += 1;
+
+            if self.result.doc_id > self.max_doc_id {
+                return Ok(None);
+            }
+
+            if let Some(child) = self.child.as_mut() {
+                if child.last_doc_id() < self.result.doc_id {
+                    child.read()?;
+                }
+
+                if child.last_doc_id() == self.result.doc_id {
+                    // present in the child: not absent, keep looking
+                    continue;
+                }
+            }
+
+            self.result.weight = self.weight;
+            return Ok(Some(&mut self.result));
+        }
+    }
+
+    // Skip to a specific docId. If the child also has a hit on this docId,
+    // the docId is absent from this (negated) iterator, so the next absent
+    // docId is reported instead via `NotFound`.
+    fn skip_to(
+        &mut self,
+        doc_id: t_docId,
+    ) -> Result<Option<SkipToOutcome<'_, 'index>>, RQEIteratorError> {
+        debug_assert!(doc_id > self.result.doc_id);
+
+        if doc_id > self.max_doc_id || self.at_eof() {
+            self.result.doc_id = self.max_doc_id;
+            return Ok(None);
+        }
+
+        if let Some(child) = self.child.as_mut() {
+            if child.last_doc_id() < doc_id {
+                child.skip_to(doc_id)?;
+            }
+
+            if child.last_doc_id() == doc_id {
+                // child contains doc_id, so it's not absent: position
+                // ourselves at doc_id before scanning forward for the next
+                // absent docId, otherwise `read` would resume from wherever
+                // we were before this call.
+                self.result.doc_id = doc_id;
+                return match self.read()? {
+                    Some(next) => Ok(Some(SkipToOutcome::NotFound(next))),
+                    None => Ok(None),
+                };
+            }
+        }
+
+        self.result.doc_id = doc_id;
+        self.result.weight = self.weight;
+        Ok(Some(SkipToOutcome::Found(&mut self.result)))
+    }
+
+    fn revalidate(&mut self) -> Result<RQEValidateStatus<'_, 'index>, RQEIteratorError> {
+        let Some(child) = self.child.as_mut() else {
+            return Ok(RQEValidateStatus::Ok);
+        };
+        let last_child_doc_id = child.last_doc_id();
+
+        match child.revalidate()? {
+            RQEValidateStatus::Aborted => {
+                // The child can no longer tell us which docIds it contains;
+                // drop it so we become a pure wildcard over the remaining
+                // range, same as `Optional` does on child abort.
+                self.child = None;
+                Ok(if last_child_doc_id != self.result.doc_id {
+                    RQEValidateStatus::Ok
+                } else {
+                    // The child was sitting on our current docId, so the
+                    // current result is not one yielded as absent; re-read
+                    // to move on now that the child is gone.
+                    RQEValidateStatus::Moved {
+                        current: self.read()?,
+                    }
+                })
+            }
+            RQEValidateStatus::Ok => Ok(RQEValidateStatus::Ok),
+            RQEValidateStatus::Moved { .. } => {
+                if child.last_doc_id() != self.result.doc_id {
+                    // Current result is not affected by the child's new position.
+                    return Ok(RQEValidateStatus::Ok);
+                }
+
+                // The child now matches our current docId, meaning our
+                // current result is stale (it should have been absent from
+                // this iterator); re-read to recover.
+                Ok(RQEValidateStatus::Moved {
+                    current: self.read()?,
+                })
+            }
+        }
+    }
+
+    #[inline(always)]
+    fn rewind(&mut self) {
+        self.result.doc_id = 0;
+        if let Some(child) = self.child.as_mut() {
+            child.rewind();
+        }
+    }
+
+    #[inline(always)]
+    fn num_estimated(&self) -> usize {
+        self.max_doc_id as usize
+    }
+
+    #[inline(always)]
+    fn last_doc_id(&self) -> t_docId {
+        self.result.doc_id
+    }
+
+    #[inline(always)]
+    fn at_eof(&self) -> bool {
+        self.result.doc_id >= self.max_doc_id
+    }
+}
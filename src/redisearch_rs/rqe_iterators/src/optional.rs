@@ -9,11 +9,29 @@
 
 //! Optional iterator implementation
 
-use ffi::{RS_FIELDMASK_ALL, t_docId};
+use ffi::{RS_FIELDMASK_ALL, t_docId, t_fieldMask};
 use inverted_index::RSIndexResult;
 
 use crate::{RQEIterator, RQEIteratorError, RQEValidateStatus, SkipToOutcome};
 
+/// Describes the `freq` and `field_mask` stamped onto every virtual result
+/// synthesized by an [`Optional`] iterator when its child has no hit for the
+/// current docId. See [`Optional::with_virtual_template`].
+#[derive(Clone, Copy, Debug)]
+pub struct VirtualResultTemplate {
+    pub freq: u32,
+    pub field_mask: t_fieldMask,
+}
+
+impl VirtualResultTemplate {
+    /// The template used by [`Optional::new`]: `freq == 1` and
+    /// `field_mask == RS_FIELDMASK_ALL`.
+    pub const DEFAULT: Self = Self {
+        freq: 1,
+        field_mask: RS_FIELDMASK_ALL,
+    };
+}
+
 /// Iterator that extends a [`RQEIterator`] up to a given upper bound
 /// by emitting virtual results after the child iterator is exhausted.
 pub struct Optional<'index, I> {
@@ -63,13 +81,34 @@ where
     ///   child [`RQEIterator`]. When the child is exhausted, the iterator
     ///   yields virtual [`RSIndexResult`] values without weight until `max_id` is reached.
     /// * `child` [`RQEIterator`] used and wrapped around by this [`Optional`] iterator
+    ///
+    /// Virtual results synthesized by this iterator use [`VirtualResultTemplate::DEFAULT`]
+    /// (`freq == 1`, `field_mask == RS_FIELDMASK_ALL`). To customize these, use
+    /// [`Optional::with_virtual_template`] instead.
     pub const fn new(max_id: t_docId, weight: f64, child: I) -> Self {
+        Self::with_virtual_template(max_id, weight, child, VirtualResultTemplate::DEFAULT)
+    }
+
+    #[inline(always)]
+    /// Creates a new [`Optional`] iterator whose synthesized virtual results
+    /// carry the `freq`/`field_mask` from `template` instead of the defaults
+    /// used by [`Optional::new`]. Useful for scoring optional clauses
+    /// differently, e.g. treating a missing match as a zero-frequency filler
+    /// that still carries a specific field mask.
+    ///
+    /// See [`Optional::new`] for the meaning of `max_id`, `weight` and `child`.
+    pub const fn with_virtual_template(
+        max_id: t_docId,
+        weight: f64,
+        child: I,
+        template: VirtualResultTemplate,
+    ) -> Self {
         Self {
             max_doc_id: max_id,
             weight,
             result: RSIndexResult::virt()
-                .frequency(1)
-                .field_mask(RS_FIELDMASK_ALL),
+                .frequency(template.freq)
+                .field_mask(template.field_mask),
             child: Some(child),
             child_result_shelved: None,
         }
@@ -141,6 +180,55 @@ where
         Ok(Some(&mut self.result))
     }
 
+    /// Overrides the default [`RQEIterator::read_into`] to amortize the cost of
+    /// synthesizing virtual results: while the next docId cannot possibly come
+    /// from the child (it lies before a shelved child result, or, with none
+    /// shelved, the child is absent or further away than the next docId), we
+    /// advance the virtual cursor and
+    /// push results in a tight loop without going through the full `read`
+    /// dispatch. Only once the virtual cursor is about to reach the child's
+    /// next docId do we fall back to a single `read` call to consult it.
+    fn read_into(
+        &mut self,
+        out: &mut Vec<RSIndexResult<'index>>,
+        limit: usize,
+    ) -> Result<usize, RQEIteratorError> {
+        let mut produced = 0;
+
+        while produced < limit && !self.at_eof() {
+            let next_doc_id = self.result.doc_id + 1;
+
+            let child_is_far = match self.child_result_shelved {
+                // A shelved child result is only due once the cursor reaches it.
+                Some(at_doc_id) => next_doc_id < at_doc_id,
+                None => self
+                    .child
+                    .as_ref()
+                    .is_none_or(|child| child.last_doc_id() > next_doc_id),
+            };
+
+            if child_is_far {
+                self.result.doc_id = next_doc_id;
+                out.push(self.result.clone());
+                produced += 1;
+                continue;
+            }
+
+            // The child might produce a result for `next_doc_id` (or we have a
+            // shelved result to reconcile); fall back to a single `read` to
+            // keep the exact semantics of real-vs-virtual and weight handling.
+            match self.read()? {
+                Some(result) => {
+                    out.push(result.clone());
+                    produced += 1;
+                }
+                None => break,
+            }
+        }
+
+        Ok(produced)
+    }
+
     // C-Code: SkipTo for OPTIONAL iterator - Non-optimized version.
     // Skip to a specific docId. If the child has a hit on this docId, return it.
     // Otherwise, return a virtual hit.
@@ -0,0 +1,144 @@
+/*
+ * Copyright (c) 2006-Present, Redis Ltd.
+ * All rights reserved.
+ *
+ * Licensed under your choice of the Redis Source Available License 2.0
+ * (RSALv2); or (b) the Server Side Public License v1 (SSPLv1); or (c) the
+ * GNU Affero General Public License v3 (AGPLv3).
+*/
+
+//! Pre-execution iterator-tree simplification.
+//!
+//! Before a query's iterator tree is executed, [`simplify`] runs once over it
+//! and rewrites provably-redundant nodes. It works analogously to a
+//! truncated backwards propagation over a control-flow graph: a single
+//! boolean fact — "this subtree produces a hit for every docId in
+//! `[1, max_doc_id]`" — is propagated upward from each node's children. The
+//! win is skipping entire child-iterator reads for subtrees that can never
+//! affect membership in the result set.
+
+use ffi::t_docId;
+
+/// A minimal, non-executing description of an iterator-tree node, used only
+/// to run the [`simplify`] pass ahead of building the real [`crate::RQEIterator`]
+/// tree.
+pub enum IteratorNode {
+    /// An [`crate::optional::Optional`] node wrapping `child` with the given
+    /// `max_doc_id`/`weight`. This is the canonical always-matching node: it
+    /// fills every gap left by `child` with virtual results up to `max_doc_id`.
+    Optional {
+        max_doc_id: t_docId,
+        weight: f64,
+        child: Box<IteratorNode>,
+    },
+    /// Intersection ("AND") of `children`: matches a docId only if every
+    /// child does.
+    Intersect(Vec<IteratorNode>),
+    /// Union ("OR") of `children`: matches a docId if any child does.
+    Union(Vec<IteratorNode>),
+    /// Any other node kind this pass does not reason about. Treated as an
+    /// opaque leaf that is not known to always match.
+    Other,
+}
+
+impl IteratorNode {
+    /// Whether this node is known to produce a hit for every docId in
+    /// `[1, max_doc_id]`, purely from its structure (no reads happen here).
+    fn always_matches(&self) -> bool {
+        match self {
+            Self::Optional { .. } => true,
+            Self::Union(children) => children.iter().any(Self::always_matches),
+            Self::Intersect(_) | Self::Other => false,
+        }
+    }
+}
+
+/// Runs a single bottom-up pass over `tree`, rewriting provably-redundant
+/// nodes:
+///
+/// 1. An [`IteratorNode::Optional`] is always-matching.
+/// 2. An always-matching child of an [`IteratorNode::Intersect`] is dropped,
+///    since it never filters the intersection.
+/// 3. When an [`IteratorNode::Union`] has more than one always-matching
+///    branch, all but one of them are dropped as redundant.
+/// 4. Directly-nested [`IteratorNode::Optional`]s collapse into a single one,
+///    carrying the min of their `max_doc_id`s and the product of their
+///    weights.
+///
+/// The resulting tree does not always keep the original's
+/// `num_estimated`/`last_doc_id` behaviour: (4) keeps only the smaller
+/// `max_doc_id`, so a collapsed Optional covers docIds up to that bound only.
+pub fn simplify(tree: IteratorNode) -> IteratorNode {
+    match tree {
+        IteratorNode::Optional {
+            max_doc_id,
+            weight,
+            child,
+        } => match simplify(*child) {
+            // (4) Collapse directly-nested Optionals into one.
+            IteratorNode::Optional {
+                max_doc_id: child_max_doc_id,
+                weight: child_weight,
+                child: grandchild,
+            } => IteratorNode::Optional {
+                max_doc_id: max_doc_id.min(child_max_doc_id),
+                weight: weight * child_weight,
+                child: grandchild,
+            },
+            child => IteratorNode::Optional {
+                max_doc_id,
+                weight,
+                child: Box::new(child),
+            },
+        },
+
+        IteratorNode::Intersect(children) => {
+            let mut filtered = Vec::with_capacity(children.len());
+            let mut always_matching = Vec::new();
+
+            // (2) Drop always-matching children; they never filter the intersection.
+            for child in children.into_iter().map(simplify) {
+                if child.always_matches() {
+                    always_matching.push(child);
+                } else {
+                    filtered.push(child);
+                }
+            }
+
+            if filtered.is_empty() {
+                // Every child always matched; keep exactly one of them so the
+                // (vacuously true) intersection isn't left with no node at all.
+                always_matching.truncate(1);
+                filtered = always_matching;
+            }
+
+            match filtered.len() {
+                1 => filtered.remove(0),
+                _ => IteratorNode::Intersect(filtered),
+            }
+        }
+
+        IteratorNode::Union(children) => {
+            let mut kept = Vec::with_capacity(children.len());
+            let mut seen_always_matching = false;
+
+            // (3) Once one always-matching branch is kept, later ones are redundant.
+            for child in children.into_iter().map(simplify) {
+                if child.always_matches() {
+                    if seen_always_matching {
+                        continue;
+                    }
+                    seen_always_matching = true;
+                }
+                kept.push(child);
+            }
+
+            match kept.len() {
+                1 => kept.remove(0),
+                _ => IteratorNode::Union(kept),
+            }
+        }
+
+        other @ IteratorNode::Other => other,
+    }
+}
@@ -0,0 +1,104 @@
+/*
+ * Copyright (c) 2006-Present, Redis Ltd.
+ * All rights reserved.
+ *
+ * Licensed under your choice of the Redis Source Available License 2.0
+ * (RSALv2); or (b) the Server Side Public License v1 (SSPLv1); or (c) the
+ * GNU Affero General Public License v3 (AGPLv3).
+*/
+
+use ffi::t_docId;
+use query_error::QueryErrorCode;
+use rqe_iterators::{
+    RQEIterator as _, SkipToOutcome,
+    federated::{Federated, FederatedSource, SourceKind},
+};
+
+mod c_mocks;
+mod utils;
+
+mod federated_iterator_tests {
+    use super::*;
+
+    fn source<const N: usize>(docs: [t_docId; N], weight: f64) -> FederatedSource<'static> {
+        FederatedSource {
+            kind: SourceKind::LocalShard,
+            weight,
+            iter: Box::new(utils::MockIterator::new(docs)),
+        }
+    }
+
+    #[test]
+    fn test_merges_sources_in_ascending_order() {
+        let mut it = Federated::new(vec![source([10, 30, 50], 1.0), source([20, 30, 40], 2.0)])
+            .expect("valid federated configuration");
+
+        let expected = [(10, 1.0), (20, 2.0), (30, 3.0), (40, 2.0), (50, 1.0)];
+
+        for (doc_id, weight) in expected {
+            let result = it.read().expect("read without error").expect("some result");
+            assert_eq!(result.doc_id, doc_id);
+            assert_eq!(result.weight, weight);
+        }
+
+        assert!(it.read().expect("no error to be returned").is_none());
+        assert!(it.at_eof());
+    }
+
+    #[test]
+    fn test_skip_to_not_found_reports_next_hit() {
+        let mut it = Federated::new(vec![source([10, 30], 1.0), source([20, 40], 1.0)])
+            .expect("valid federated configuration");
+
+        match it
+            .skip_to(25)
+            .expect("no error to be returned while skipping")
+        {
+            Some(SkipToOutcome::NotFound(result)) => assert_eq!(result.doc_id, 30),
+            outcome => panic!("unexpected outcome: {outcome:?}"),
+        }
+    }
+
+    #[test]
+    fn test_single_default_weighted_source_is_valid() {
+        let result = Federated::new(vec![FederatedSource {
+            kind: SourceKind::LocalShard,
+            weight: 1.0,
+            iter: Box::new(utils::MockIterator::new([1])),
+        }])
+        .map(drop);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_weighted_ephemeral_source_is_rejected() {
+        let result = Federated::new(vec![FederatedSource {
+            kind: SourceKind::Ephemeral,
+            weight: 2.0,
+            iter: Box::new(utils::MockIterator::new([1])),
+        }])
+        .map(drop);
+
+        assert_eq!(result, Err(QueryErrorCode::WeightNotAllowed));
+    }
+
+    #[test]
+    fn test_ephemeral_and_remote_sources_are_incompatible() {
+        let result = Federated::new(vec![
+            FederatedSource {
+                kind: SourceKind::Ephemeral,
+                weight: 1.0,
+                iter: Box::new(utils::MockIterator::new([1])),
+            },
+            FederatedSource {
+                kind: SourceKind::RemoteIndex,
+                weight: 1.0,
+                iter: Box::new(utils::MockIterator::new([2])),
+            },
+        ])
+        .map(drop);
+
+        assert_eq!(result, Err(QueryErrorCode::IncompatibleFederatedSources));
+    }
+}
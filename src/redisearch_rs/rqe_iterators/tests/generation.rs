@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) 2006-Present, Redis Ltd.
+ * All rights reserved.
+ *
+ * Licensed under your choice of the Redis Source Available License 2.0
+ * (RSALv2); or (b) the Server Side Public License v1 (SSPLv1); or (c) the
+ * GNU Affero General Public License v3 (AGPLv3).
+*/
+
+use ffi::t_docId;
+use rqe_iterators::{
+    RQEIterator as _, RQEValidateStatus,
+    generation::{GenerationCounter, GenerationGuarded},
+};
+
+mod c_mocks;
+mod utils;
+
+mod generation_tests {
+    use super::*;
+
+    #[test]
+    fn test_fresh_counter_matches_itself() {
+        let counter = GenerationCounter::new();
+        let captured = counter.current();
+
+        assert!(captured.matches(counter.current()));
+    }
+
+    #[test]
+    fn test_bump_invalidates_captured_generation() {
+        let counter = GenerationCounter::new();
+        let captured = counter.current();
+
+        counter.bump();
+
+        assert!(!captured.matches(counter.current()));
+    }
+}
+
+// Exercises `GenerationGuarded`, the decorator that retrofits a leaf
+// `RQEIterator` with the generation check described above.
+mod generation_guarded_leaf_test {
+    use super::*;
+
+    const NUM_DOCS: usize = 3;
+    const DOCS: [t_docId; NUM_DOCS] = [1, 2, 3];
+
+    #[test]
+    fn test_revalidate_ok_when_generation_unchanged() {
+        let counter = GenerationCounter::new();
+        let mut it = GenerationGuarded::new(&counter, utils::MockIterator::new(DOCS));
+
+        let status = it.revalidate().expect("revalidate without error");
+        assert!(matches!(status, RQEValidateStatus::Ok));
+    }
+
+    #[test]
+    fn test_revalidate_aborts_after_generation_bump() {
+        let counter = GenerationCounter::new();
+        let mut it = GenerationGuarded::new(&counter, utils::MockIterator::new(DOCS));
+
+        counter.bump();
+
+        // The index moved on to a new generation, so the leaf must abort
+        // without ever being consulted, regardless of its own state.
+        let status = it.revalidate().expect("revalidate without error");
+        assert!(matches!(status, RQEValidateStatus::Aborted));
+    }
+
+    #[test]
+    fn test_revalidate_delegates_to_leaf_when_generation_matches() {
+        let counter = GenerationCounter::new();
+        let child = utils::MockIterator::new(DOCS);
+        let mut data = child.data();
+        let mut it = GenerationGuarded::new(&counter, child);
+
+        data.set_revalidate_result(utils::MockRevalidateResult::Abort);
+
+        let status = it.revalidate().expect("revalidate without error");
+        assert!(matches!(status, RQEValidateStatus::Aborted));
+        assert_eq!(data.revalidate_count(), 1);
+    }
+}
@@ -0,0 +1,263 @@
+/*
+ * Copyright (c) 2006-Present, Redis Ltd.
+ * All rights reserved.
+ *
+ * Licensed under your choice of the Redis Source Available License 2.0
+ * (RSALv2); or (b) the Server Side Public License v1 (SSPLv1); or (c) the
+ * GNU Affero General Public License v3 (AGPLv3).
+*/
+
+use ffi::t_docId;
+use rqe_iterators::{RQEIterator as _, RQEValidateStatus, SkipToOutcome, empty::Empty, not::Not};
+
+mod c_mocks;
+mod utils;
+
+// port of NotIteratorTest + its `TEST_F` usage
+mod not_iterator_tests {
+    use super::*;
+
+    const MAX_DOC_ID: t_docId = 100;
+    const WEIGHT: f64 = 2.;
+
+    const NUM_DOCS: usize = 5;
+    const CHILD_DOCS: [t_docId; NUM_DOCS] = [10, 20, 30, 50, 80];
+
+    fn setup_not_iterator_with_mock_child<'index>()
+    -> Not<'index, utils::MockIterator<'index, NUM_DOCS>> {
+        let child = utils::MockIterator::new(CHILD_DOCS);
+
+        Not::new(MAX_DOC_ID, WEIGHT, child)
+    }
+
+    #[test]
+    fn test_cpp_read_complement() {
+        let mut it = setup_not_iterator_with_mock_child();
+
+        assert_eq!(MAX_DOC_ID as usize, it.num_estimated());
+
+        for expected_id in 1..=MAX_DOC_ID {
+            if CHILD_DOCS.contains(&expected_id) {
+                continue;
+            }
+
+            let outcome = it.read().expect("read without error").expect("some result");
+            assert_eq!(outcome.doc_id, expected_id);
+            assert_eq!(outcome.weight, WEIGHT);
+            assert_eq!(it.last_doc_id(), expected_id);
+        }
+
+        assert!(it.read().expect("no error to be returned").is_none());
+        assert!(it.at_eof());
+    }
+
+    #[test]
+    fn test_cpp_skip_to_absent_doc() {
+        let mut it = setup_not_iterator_with_mock_child();
+
+        const SKIP_TO_DOC_ID: t_docId = 25;
+
+        match it
+            .skip_to(SKIP_TO_DOC_ID)
+            .expect("no error to be returned while skipping")
+        {
+            Some(SkipToOutcome::Found(result)) => {
+                assert_eq!(result.doc_id, SKIP_TO_DOC_ID);
+                assert_eq!(result.weight, WEIGHT);
+            }
+            outcome => panic!("unexpected outcome: {outcome:?}"),
+        }
+
+        assert_eq!(it.last_doc_id(), SKIP_TO_DOC_ID);
+    }
+
+    #[test]
+    fn test_cpp_skip_to_present_doc_reports_not_found() {
+        let mut it = setup_not_iterator_with_mock_child();
+
+        const SKIP_TO_DOC_ID: t_docId = 20;
+
+        match it
+            .skip_to(SKIP_TO_DOC_ID)
+            .expect("no error to be returned while skipping")
+        {
+            Some(SkipToOutcome::NotFound(result)) => {
+                // The next absent docId after 20 is 21.
+                assert_eq!(result.doc_id, 21);
+            }
+            outcome => panic!("unexpected outcome: {outcome:?}"),
+        }
+    }
+
+    #[test]
+    fn test_cpp_rewind_behavior() {
+        let mut it = setup_not_iterator_with_mock_child();
+
+        for _ in 0..5 {
+            let _ = it
+                .read()
+                .expect("read without error")
+                .expect("read some result");
+        }
+
+        it.rewind();
+        assert_eq!(it.last_doc_id(), 0);
+        assert!(!it.at_eof());
+    }
+
+    #[test]
+    fn test_cpp_eof_behavior() {
+        let mut it = setup_not_iterator_with_mock_child();
+
+        match it
+            .skip_to(MAX_DOC_ID)
+            .expect("no error to be returned while skipping")
+        {
+            Some(SkipToOutcome::Found(result)) | Some(SkipToOutcome::NotFound(result)) => {
+                assert_eq!(result.doc_id, MAX_DOC_ID);
+            }
+            None => panic!("expected a result at MAX_DOC_ID"),
+        }
+
+        assert!(it.read().expect("no error to be returned").is_none());
+        assert!(it.at_eof());
+    }
+}
+
+// Port of NotIteratorWithEmptyChildTest and `TEST_F` usage
+mod not_iterator_with_empty_child_test {
+    use super::*;
+
+    const MAX_DOC_ID: t_docId = 50;
+    const WEIGHT: f64 = 3.;
+
+    fn setup_not_iterator_with_empty_child<'index>() -> Not<'index, Empty> {
+        let child = Empty::default();
+
+        Not::new(MAX_DOC_ID, WEIGHT, child)
+    }
+
+    #[test]
+    fn test_cpp_read_all_results() {
+        let mut it = setup_not_iterator_with_empty_child();
+
+        // An empty child is absent everywhere, so every docId up to
+        // max_doc_id is yielded.
+        for expected_id in 1..=MAX_DOC_ID {
+            let result = it
+                .read()
+                .expect("read without error")
+                .expect("read some result");
+            assert_eq!(result.doc_id, expected_id);
+            assert_eq!(result.weight, WEIGHT);
+        }
+
+        assert!(it.read().expect("no error to be returned").is_none());
+        assert!(it.at_eof());
+    }
+}
+
+// port of NotIteratorRevalidateTest and its `TEST_F` usage, mirroring
+// `optional_iterator_revalidate_test` for the sibling `Optional` iterator.
+mod not_iterator_revalidate_test {
+    use super::*;
+
+    const MAX_DOC_ID: t_docId = 100;
+    const WEIGHT: f64 = 2.;
+
+    const NUM_DOCS: usize = 5;
+    const CHILD_DOCS: [t_docId; NUM_DOCS] = [10, 20, 30, 50, 80];
+
+    fn setup_not_iterator_with_mock_child_and_data<'index>() -> (
+        Not<'index, utils::MockIterator<'index, NUM_DOCS>>,
+        utils::MockData,
+    ) {
+        let child = utils::MockIterator::new(CHILD_DOCS);
+        let data = child.data();
+
+        let it = Not::new(MAX_DOC_ID, WEIGHT, child);
+
+        (it, data)
+    }
+
+    #[test]
+    fn test_cpp_revalidate_ok() {
+        let (mut it, mut data) = setup_not_iterator_with_mock_child_and_data();
+
+        data.set_revalidate_result(utils::MockRevalidateResult::Ok);
+
+        let _ = it
+            .read()
+            .expect("read without error")
+            .expect("read some result");
+        let _ = it
+            .read()
+            .expect("read without error")
+            .expect("read some result");
+
+        let status = it.revalidate().expect("revalidate without error");
+        assert!(matches!(status, RQEValidateStatus::Ok));
+        assert_eq!(data.revalidate_count(), 1);
+
+        let _ = it
+            .read()
+            .expect("read without error after revalidate")
+            .expect("read some result after revalidate");
+    }
+
+    #[test]
+    fn test_cpp_revalidate_aborted_turns_into_pure_wildcard() {
+        let (mut it, mut data) = setup_not_iterator_with_mock_child_and_data();
+
+        data.set_revalidate_result(utils::MockRevalidateResult::Abort);
+
+        let _ = it
+            .read()
+            .expect("read without error")
+            .expect("read some result");
+
+        // Not iterator handles child abort gracefully by dropping it, not by
+        // continuing to call into it.
+        let status = it.revalidate().expect("revalidate without error");
+        assert!(matches!(status, RQEValidateStatus::Ok));
+
+        // After the abort the child is gone, so every remaining docId
+        // (including ones that used to be in CHILD_DOCS) is now yielded.
+        for _ in 0..(MAX_DOC_ID - it.last_doc_id()) {
+            let result = it
+                .read()
+                .expect("read without error after revalidate")
+                .expect("read some result after revalidate");
+            assert_eq!(result.weight, WEIGHT);
+        }
+
+        assert!(it.read().expect("no error to be returned").is_none());
+    }
+
+    #[test]
+    fn test_cpp_revalidate_moved() {
+        let (mut it, mut data) = setup_not_iterator_with_mock_child_and_data();
+
+        data.set_revalidate_result(utils::MockRevalidateResult::Move);
+
+        const DOC_ID: t_docId = 15;
+        match it
+            .skip_to(DOC_ID)
+            .expect("no error to be returned while skipping")
+        {
+            Some(SkipToOutcome::Found(result)) => assert_eq!(result.doc_id, DOC_ID),
+            outcome => panic!("unexpected outcome: {outcome:?}"),
+        }
+        assert_eq!(it.last_doc_id(), DOC_ID);
+
+        let status = it.revalidate().expect("revalidate without error");
+        assert!(matches!(
+            status,
+            RQEValidateStatus::Ok | RQEValidateStatus::Moved { .. }
+        ));
+
+        let _ = it
+            .read()
+            .expect("read returns either some result or EOF after revalidate");
+    }
+}
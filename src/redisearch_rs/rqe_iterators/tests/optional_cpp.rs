@@ -7,9 +7,11 @@
  * GNU Affero General Public License v3 (AGPLv3).
 */
 
-use ffi::{RS_FIELDMASK_ALL, t_docId};
+use ffi::{RS_FIELDMASK_ALL, t_docId, t_fieldMask};
 use rqe_iterators::{
-    RQEIterator as _, RQEValidateStatus, SkipToOutcome, empty::Empty, optional::Optional,
+    RQEIterator as _, RQEValidateStatus, SkipToOutcome,
+    empty::Empty,
+    optional::{Optional, VirtualResultTemplate},
 };
 
 mod c_mocks;
@@ -636,3 +638,114 @@ mod optional_iterator_revalidate_test {
             .expect("read some result after revalidate");
     }
 }
+
+// Exercises the batched `RQEIterator::read_into` override added alongside
+// `read`, asserting it produces the exact same sequence (including the
+// child-shelving edge case where a real hit surfaces ahead of the next
+// virtual docId and has to be held back).
+mod optional_iterator_read_into_test {
+    use super::*;
+
+    const MAX_DOC_ID: t_docId = 100;
+    const WEIGHT: f64 = 2.;
+
+    const NUM_DOCS: usize = 5;
+    const CHILD_DOCS: [t_docId; NUM_DOCS] = [10, 20, 30, 50, 80];
+
+    fn setup_optional_iterator_with_mock_child<'index>()
+    -> Optional<'index, utils::MockIterator<'index, NUM_DOCS>> {
+        let child = utils::MockIterator::new(CHILD_DOCS);
+
+        Optional::new(MAX_DOC_ID, WEIGHT, child)
+    }
+
+    #[test]
+    fn test_cpp_read_into_matches_read_sequence() {
+        let mut batched = setup_optional_iterator_with_mock_child();
+        let mut single = setup_optional_iterator_with_mock_child();
+
+        let mut out = Vec::new();
+        let produced = batched
+            .read_into(&mut out, MAX_DOC_ID as usize)
+            .expect("read_into without error");
+
+        assert_eq!(produced, MAX_DOC_ID as usize);
+        assert_eq!(out.len(), MAX_DOC_ID as usize);
+
+        for result in &out {
+            let expected = single
+                .read()
+                .expect("read without error")
+                .expect("some result");
+            assert_eq!(result.doc_id, expected.doc_id);
+            assert_eq!(result.weight, expected.weight);
+            assert_eq!(result.freq, expected.freq);
+            assert_eq!(result.field_mask, expected.field_mask);
+        }
+
+        assert!(batched.at_eof());
+        assert!(single.read().expect("no error to be returned").is_none());
+    }
+
+    #[test]
+    fn test_cpp_read_into_respects_limit() {
+        let mut it = setup_optional_iterator_with_mock_child();
+
+        const LIMIT: usize = 7;
+        let mut out = Vec::new();
+        let produced = it
+            .read_into(&mut out, LIMIT)
+            .expect("read_into without error");
+
+        assert_eq!(produced, LIMIT);
+        assert_eq!(out.len(), LIMIT);
+        for (expected_id, result) in (1..=LIMIT as t_docId).zip(&out) {
+            assert_eq!(result.doc_id, expected_id);
+        }
+        assert_eq!(it.last_doc_id(), LIMIT as t_docId);
+    }
+}
+
+// Exercises `Optional::with_virtual_template`, confirming the configured
+// `freq`/`field_mask` (instead of `Optional::new`'s hardcoded defaults) show
+// up on every virtual result.
+mod optional_iterator_virtual_template_test {
+    use super::*;
+
+    const MAX_DOC_ID: t_docId = 20;
+    const WEIGHT: f64 = 1.5;
+    const CUSTOM_FREQ: u32 = 7;
+    const CUSTOM_FIELD_MASK: t_fieldMask = 0b101;
+
+    fn setup_optional_iterator_with_custom_template<'index>() -> Optional<'index, Empty> {
+        let child = Empty::default();
+
+        Optional::with_virtual_template(
+            MAX_DOC_ID,
+            WEIGHT,
+            child,
+            VirtualResultTemplate {
+                freq: CUSTOM_FREQ,
+                field_mask: CUSTOM_FIELD_MASK,
+            },
+        )
+    }
+
+    #[test]
+    fn test_cpp_virtual_results_use_custom_template() {
+        let mut it = setup_optional_iterator_with_custom_template();
+
+        for expected_id in 1..=MAX_DOC_ID {
+            let result = it
+                .read()
+                .expect("read without error")
+                .expect("read some result, be it virtual or real");
+            assert_eq!(result.doc_id, expected_id);
+            assert_eq!(result.weight, 0.);
+            assert_eq!(result.freq, CUSTOM_FREQ);
+            assert_eq!(result.field_mask, CUSTOM_FIELD_MASK);
+        }
+
+        assert!(it.read().expect("no error to be returned").is_none());
+    }
+}
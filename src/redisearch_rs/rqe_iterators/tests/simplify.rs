@@ -0,0 +1,101 @@
+/*
+ * Copyright (c) 2006-Present, Redis Ltd.
+ * All rights reserved.
+ *
+ * Licensed under your choice of the Redis Source Available License 2.0
+ * (RSALv2); or (b) the Server Side Public License v1 (SSPLv1); or (c) the
+ * GNU Affero General Public License v3 (AGPLv3).
+*/
+
+use ffi::t_docId;
+use rqe_iterators::simplify::{IteratorNode, simplify};
+
+mod simplify_tests {
+    use super::*;
+
+    fn optional(max_doc_id: t_docId, weight: f64, child: IteratorNode) -> IteratorNode {
+        IteratorNode::Optional {
+            max_doc_id,
+            weight,
+            child: Box::new(child),
+        }
+    }
+
+    #[test]
+    fn test_optional_child_dropped_from_intersect() {
+        let tree = IteratorNode::Intersect(vec![
+            IteratorNode::Other,
+            optional(100, 1., IteratorNode::Other),
+        ]);
+
+        match simplify(tree) {
+            IteratorNode::Other => {}
+            _ => panic!(
+                "expected the always-matching Optional to be dropped, leaving the other child"
+            ),
+        }
+    }
+
+    #[test]
+    fn test_intersect_of_only_always_matching_children_keeps_one() {
+        let tree = IteratorNode::Intersect(vec![
+            optional(100, 1., IteratorNode::Other),
+            optional(200, 2., IteratorNode::Other),
+        ]);
+
+        match simplify(tree) {
+            IteratorNode::Optional { .. } => {}
+            _ => panic!("expected a single Optional node to survive"),
+        }
+    }
+
+    #[test]
+    fn test_union_collapses_redundant_always_matching_branches() {
+        let tree = IteratorNode::Union(vec![
+            IteratorNode::Other,
+            optional(100, 1., IteratorNode::Other),
+            optional(200, 2., IteratorNode::Other),
+        ]);
+
+        match simplify(tree) {
+            IteratorNode::Union(children) => {
+                assert_eq!(
+                    children.len(),
+                    2,
+                    "only one always-matching branch should remain"
+                );
+            }
+            other => panic!("expected a Union to remain, got {}", describe(&other)),
+        }
+    }
+
+    #[test]
+    fn test_nested_optionals_collapse() {
+        let tree = optional(100, 2., optional(50, 3., IteratorNode::Other));
+
+        match simplify(tree) {
+            IteratorNode::Optional {
+                max_doc_id,
+                weight,
+                child,
+            } => {
+                assert_eq!(max_doc_id, 50);
+                assert_eq!(weight, 6.);
+                assert!(matches!(*child, IteratorNode::Other));
+            }
+            other => panic!(
+                "expected a single collapsed Optional, got {}",
+                describe(&other)
+            ),
+        }
+    }
+
+    fn describe(node: &IteratorNode) -> &'static str {
+        match node {
+            IteratorNode::Optional { .. } => "Optional",
+            IteratorNode::Intersect(_) => "Intersect",
+            IteratorNode::Union(_) => "Union",
+            IteratorNode::Other => "Other",
+        }
+    }
+}